@@ -1,24 +1,41 @@
-use std::{iter::Step, mem};
-
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::Step;
+use core::mem;
+
+use serde_big_array::BigArray;
 use serde_derive::{Deserialize, Serialize};
 
-use crate::timestamp::Timestamp;
+use crate::timestamp::{Clock, Timestamp};
 
 pub const IDENTIFIER_SIZE: usize = mem::size_of::<u64>();
 
+/// A counter/identifier whose `id` is a fixed-width, big-endian byte array.
+///
+/// `N` is the width in bytes of the identifier, so `ByteCounter<8>` behaves
+/// like the original 64-bit counter. The original 128-bit `BigID` is its
+/// own type again (see [`crate::id::BigID`]): its wire format has no
+/// `timestamp` field, so it isn't interchangeable with `ByteCounter<16>`.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
-pub struct ByteCounter {
+pub struct ByteCounter<const N: usize = IDENTIFIER_SIZE> {
     pub prefix: Option<String>,
     pub timestamp: Timestamp,
-    pub id: [u8; IDENTIFIER_SIZE],
+    #[serde(with = "BigArray")]
+    pub id: [u8; N],
 
     #[serde(skip_serializing, skip_deserializing)]
     pub valid: bool,
 }
 
-impl Step for ByteCounter {
+impl<const N: usize> Step for ByteCounter<N> {
     fn steps_between(start: &Self, end: &Self) -> Option<usize> {
-        let diff = end.to_u128() - start.to_u128();
+        if end.to_u128() < start.to_u128() {
+            return None;
+        }
+        let diff = start.distance(end);
 
         if diff > usize::MAX as u128 {
             return None;
@@ -27,51 +44,132 @@ impl Step for ByteCounter {
     }
 
     fn forward_checked(start: Self, count: usize) -> Option<Self> {
-        let mut result = start;
-        for _ in 0..count {
-            result = result.next_id();
+        if count == 0 {
+            return Some(start);
         }
-        Some(result)
+
+        let id = add_with_carry(&start.id, count)?;
+
+        Some(Self {
+            prefix: start.prefix,
+            timestamp: Timestamp::default(),
+            id,
+            valid: true,
+        })
     }
 
     fn backward_checked(start: Self, count: usize) -> Option<Self> {
-        let mut result = start;
-        for _ in 0..count {
-            result = result.prev_id();
+        if count == 0 {
+            return Some(start);
+        }
+
+        let id = sub_with_borrow(&start.id, count)?;
+
+        Some(Self {
+            prefix: start.prefix,
+            timestamp: Timestamp::default(),
+            id,
+            valid: true,
+        })
+    }
+}
+
+/// Adds `count` to the big-endian base-256 integer `id`, propagating carry
+/// across the array in a single O(N) pass. Returns `None` if the result
+/// would overflow past `[u8::MAX; N]`.
+fn add_with_carry<const N: usize>(id: &[u8; N], count: usize) -> Option<[u8; N]> {
+    let mut result = *id;
+    let mut carry = count as u128;
+    let mut idx = N;
+
+    while carry > 0 {
+        if idx == 0 {
+            return None;
+        }
+        idx -= 1;
+
+        let sum = result[idx] as u128 + (carry & 0xFF);
+        result[idx] = sum as u8;
+        carry = (carry >> 8) + (sum >> 8);
+    }
+
+    Some(result)
+}
+
+/// Subtracts `count` from the big-endian base-256 integer `id`, propagating
+/// borrow across the array in a single O(N) pass. Returns `None` if the
+/// result would underflow past `[0; N]`.
+fn sub_with_borrow<const N: usize>(id: &[u8; N], count: usize) -> Option<[u8; N]> {
+    let mut result = *id;
+    let mut borrow = count as u128;
+    let mut idx = N;
+
+    while borrow > 0 {
+        if idx == 0 {
+            return None;
+        }
+        idx -= 1;
+
+        let byte = result[idx] as i128 - (borrow & 0xFF) as i128;
+        if byte < 0 {
+            result[idx] = (byte + 0x100) as u8;
+            borrow = (borrow >> 8) + 1;
+        } else {
+            result[idx] = byte as u8;
+            borrow >>= 8;
         }
-        Some(result)
     }
+
+    Some(result)
 }
 
-impl ByteCounter {
+impl<const N: usize> ByteCounter<N> {
     pub fn new() -> Self {
-        ByteCounter::default()
+        Self::default()
     }
 
     pub fn new_with_prefix(prefix: String) -> Self {
-        let mut id = ByteCounter::default();
+        let mut id = Self::default();
         id.prefix = Some(prefix);
         id
     }
 
-    pub fn decode_bytes(str_bytes: &str) -> Self {
+    /// Like [`ByteCounter::new`], but timestamps the counter using `clock`
+    /// instead of the system clock. Works under `no_std` and makes the
+    /// timestamp deterministic in tests.
+    pub fn new_with_clock(clock: &impl Clock) -> Self {
+        Self {
+            prefix: None,
+            timestamp: Timestamp::from_clock(clock),
+            id: [0; N],
+            valid: true,
+        }
+    }
+
+    /// Parses the `to_string()` decimal-triplet `id` encoding back into
+    /// bytes, returning a [`DecodeError`] instead of panicking on
+    /// malformed input.
+    pub fn decode_bytes(str_bytes: &str) -> Result<Self, DecodeError> {
+        if !str_bytes.len().is_multiple_of(3) {
+            return Err(DecodeError::Malformed);
+        }
+
         let byte_count = str_bytes.len() / 3;
-        let _aligned = match byte_count.cmp(&IDENTIFIER_SIZE) {
-            std::cmp::Ordering::Less => {
-                let prefix_size = IDENTIFIER_SIZE - byte_count;
-                let result = format!(
+        let _aligned = match byte_count.cmp(&N) {
+            core::cmp::Ordering::Less => {
+                let prefix_size = N - byte_count;
+                format!(
                     "{0}{1}",
-                    String::from_utf8(vec![b'0'; prefix_size * 3]).unwrap(),
+                    String::from_utf8(vec![b'0'; prefix_size * 3]).map_err(DecodeError::InvalidUtf8)?,
                     str_bytes
-                );
-                result
+                )
             }
-            std::cmp::Ordering::Equal => str_bytes.to_string(),
-            std::cmp::Ordering::Greater => {
-                let start_idx = str_bytes.len() - (IDENTIFIER_SIZE * 3);
+            core::cmp::Ordering::Equal => str_bytes.to_string(),
+            core::cmp::Ordering::Greater => {
+                let start_idx = str_bytes.len() - (N * 3);
                 let sl = str_bytes.as_bytes().to_owned();
                 let slice = &sl[start_idx..];
-                String::from_utf8(slice.to_vec()).unwrap()
+                String::from_utf8(slice.to_vec()).map_err(DecodeError::InvalidUtf8)?
             }
         };
 
@@ -79,35 +177,44 @@ impl ByteCounter {
             .chars()
             .collect::<Vec<char>>()
             .chunks(3)
-            .map(|c| c.iter().collect::<String>().parse::<u8>().unwrap())
-            .collect::<Vec<u8>>();
+            .map(|c| {
+                c.iter()
+                    .collect::<String>()
+                    .parse::<u8>()
+                    .map_err(|_| DecodeError::InvalidDigits(c.iter().collect()))
+            })
+            .collect::<Result<Vec<u8>, DecodeError>>()?;
+
+        if __bytes.len() != N {
+            return Err(DecodeError::Malformed);
+        }
 
-        let mut mem_id = [0x0_u8; IDENTIFIER_SIZE];
+        let mut mem_id = [0x0_u8; N];
         mem_id.clone_from_slice(__bytes.as_ref());
 
-        Self {
+        Ok(Self {
             prefix: None,
-            timestamp: Timestamp::new(),
+            timestamp: Timestamp::default(),
             id: mem_id,
             valid: true,
-        }
+        })
     }
 }
 
-impl Default for ByteCounter {
+impl<const N: usize> Default for ByteCounter<N> {
     fn default() -> Self {
-        let id = [0; IDENTIFIER_SIZE];
+        let id = [0; N];
 
         Self {
             id,
-            timestamp: Timestamp::new(),
+            timestamp: Timestamp::default(),
             prefix: None,
             valid: true,
         }
     }
 }
 
-impl ToString for ByteCounter {
+impl<const N: usize> ToString for ByteCounter<N> {
     fn to_string(&self) -> String {
         let _id = self
             .id
@@ -137,8 +244,10 @@ impl ToString for ByteCounter {
     }
 }
 
-impl From<&String> for ByteCounter {
-    fn from(key: &String) -> Self {
+impl<const N: usize> TryFrom<&String> for ByteCounter<N> {
+    type Error = DecodeError;
+
+    fn try_from(key: &String) -> Result<Self, DecodeError> {
         let mut parts = key.split(':').collect::<Vec<&str>>();
 
         if parts.len() == 3 {
@@ -146,55 +255,287 @@ impl From<&String> for ByteCounter {
             let timestamp = parts.remove(0);
             let id = parts.remove(0);
 
-            let mut obj = ByteCounter::decode_bytes(id);
+            let mut obj = Self::decode_bytes(id)?;
             obj.prefix = Some(prefix.to_string());
             obj.timestamp = Timestamp::from(timestamp);
             if obj.timestamp.value() == 0 {
                 obj.valid = false;
             }
 
-            return obj;
+            return Ok(obj);
         }
 
         if parts.len() == 2 {
             let timestamp = parts.remove(0);
             let id = parts.remove(0);
-            let mut obj = ByteCounter::decode_bytes(id);
+            let mut obj = Self::decode_bytes(id)?;
             obj.timestamp = Timestamp::from(timestamp);
             if obj.timestamp.value() == 0 {
                 obj.valid = false;
             }
 
-            return obj;
+            return Ok(obj);
         }
 
-        return Self {
+        Ok(Self {
             prefix: None,
-            timestamp: Timestamp::new(),
-            id: [0; IDENTIFIER_SIZE],
+            timestamp: Timestamp::default(),
+            id: [0; N],
             valid: false,
+        })
+    }
+}
+
+/// Leading type tag for the memcmp-ordered key encoding produced by
+/// [`ByteCounter::to_key_bytes`].
+pub const KEY_TAG: u8 = 0x01;
+
+impl<const N: usize> ByteCounter<N> {
+    /// Encodes this counter as an order-preserving binary key: a type tag,
+    /// the prefix as UTF-8 terminated by `0x00 0x00` (interior `0x00` bytes
+    /// are escaped as `0x00 0xFF`, which sorts after the terminator, so no
+    /// encoded prefix is a byte-prefix of another and a prefix sorts before
+    /// any longer prefix that extends it), the timestamp as fixed 8-byte
+    /// big-endian, then the raw
+    /// (already big-endian) `id` bytes.
+    ///
+    /// Two keys compare in the same order as the logical
+    /// `(prefix, timestamp, id)` tuple, which makes this suitable for use
+    /// as a key in an LSM/B-tree store that orders keys by `memcmp`.
+    pub fn to_key_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(1 + N + 8);
+        out.push(KEY_TAG);
+
+        if let Some(prefix) = &self.prefix {
+            for byte in prefix.as_bytes() {
+                if *byte == 0x00 {
+                    out.push(0x00);
+                    out.push(0xFF);
+                } else {
+                    out.push(*byte);
+                }
+            }
+        }
+        out.push(0x00);
+        out.push(0x00);
+
+        out.extend_from_slice(&self.timestamp.value().to_be_bytes());
+        out.extend_from_slice(&self.id);
+        out
+    }
+
+    /// Reverses [`ByteCounter::to_key_bytes`].
+    ///
+    /// Returns a [`DecodeError`] instead of panicking if `bytes` is not a
+    /// well-formed key produced by `to_key_bytes` (missing tag,
+    /// unterminated prefix, or truncated timestamp/id) — callers scanning
+    /// untrusted keys (e.g. an LSM/B-tree range scan) can skip or report a
+    /// single corrupt key instead of aborting the whole scan.
+    pub fn from_key_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.is_empty() {
+            return Err(DecodeError::Truncated);
+        }
+        let rest = &bytes[1..];
+
+        let mut prefix_bytes = Vec::new();
+        let mut idx = 0;
+        loop {
+            if idx >= rest.len() {
+                return Err(DecodeError::Truncated);
+            }
+            match rest[idx] {
+                0x00 if rest.get(idx + 1) == Some(&0xFF) => {
+                    prefix_bytes.push(0x00);
+                    idx += 2;
+                }
+                0x00 if rest.get(idx + 1) == Some(&0x00) => {
+                    idx += 2;
+                    break;
+                }
+                0x00 => return Err(DecodeError::Malformed),
+                byte => {
+                    prefix_bytes.push(byte);
+                    idx += 1;
+                }
+            }
+        }
+
+        let timestamp_bytes = rest.get(idx..idx + 8).ok_or(DecodeError::Truncated)?;
+        let mut timestamp_buf = [0u8; 8];
+        timestamp_buf.clone_from_slice(timestamp_bytes);
+        idx += 8;
+
+        let id_bytes = rest.get(idx..idx + N).ok_or(DecodeError::Truncated)?;
+        let mut id = [0u8; N];
+        id.clone_from_slice(id_bytes);
+
+        let prefix = if prefix_bytes.is_empty() {
+            None
+        } else {
+            Some(String::from_utf8(prefix_bytes).map_err(DecodeError::InvalidUtf8)?)
         };
+
+        Ok(Self {
+            prefix,
+            timestamp: Timestamp::from(u64::from_be_bytes(timestamp_buf)),
+            id,
+            valid: true,
+        })
+    }
+}
+
+/// Error returned by the decoding functions ([`ByteCounter::decode`],
+/// [`ByteCounter::decode_bytes`], [`ByteCounter::from_key_bytes`], the
+/// `TryFrom` impls, and their [`crate::id::BigID`] counterparts) when input
+/// is not a well-formed encoding, and by [`ByteCounter::encode`] when a
+/// field is too long for its length prefix.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The input ended before a field (a length-prefixed or terminated
+    /// field, or the fixed-width timestamp or id) could be read in full.
+    Truncated,
+    /// The prefix bytes, or in `decode_bytes` the cut id digit string, are
+    /// not valid UTF-8.
+    InvalidUtf8(alloc::string::FromUtf8Error),
+    /// A decimal digit triplet in a `decode_bytes` string is not `0..=255`.
+    InvalidDigits(String),
+    /// The encoded id is wider than `N` bytes and cannot fit.
+    IdTooWide { max: usize, actual: usize },
+    /// The input is not a whole number of 3-digit triplets (`decode_bytes`),
+    /// or has a malformed escape sequence in its prefix (`from_key_bytes`).
+    Malformed,
+    /// A length-prefixed field in `encode` is longer than 255 bytes and
+    /// can't be represented by its single-byte length prefix.
+    FieldTooLong { field: &'static str, len: usize },
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodeError::Truncated => write!(f, "byte counter encoding is truncated"),
+            DecodeError::InvalidUtf8(err) => write!(f, "invalid UTF-8 in encoded bytes: {err}"),
+            DecodeError::InvalidDigits(digits) => {
+                write!(f, "invalid decimal digit triplet: {digits:?}")
+            }
+            DecodeError::IdTooWide { max, actual } => {
+                write!(f, "encoded id is {actual} bytes, which does not fit in {max}")
+            }
+            DecodeError::Malformed => {
+                write!(f, "input is not a whole number of 3-digit triplets, or has a malformed key-prefix escape")
+            }
+            DecodeError::FieldTooLong { field, len } => {
+                write!(f, "{field} is {len} bytes, which does not fit in a u8 length prefix")
+            }
+        }
     }
 }
 
-impl ByteCounter {
+impl core::error::Error for DecodeError {}
+
+impl<const N: usize> ByteCounter<N> {
+    /// Encodes this counter as a compact binary blob: a length-prefixed
+    /// UTF-8 prefix, the timestamp as fixed 8-byte big-endian, then a
+    /// single length byte followed by the significant (leading-zero
+    /// trimmed) big-endian bytes of `id`.
+    ///
+    /// This is much smaller than [`ByteCounter::to_string`] for small ids
+    /// and is the canonical wire format; use `to_string` only for the
+    /// human-readable form.
+    ///
+    /// Returns [`DecodeError::FieldTooLong`] if the prefix or the
+    /// significant id bytes are longer than 255 bytes, since each is
+    /// framed by a single-byte length prefix.
+    pub fn encode(&self) -> Result<Vec<u8>, DecodeError> {
+        let prefix_bytes = self.prefix.as_deref().unwrap_or("").as_bytes();
+        let prefix_len = u8::try_from(prefix_bytes.len()).map_err(|_| DecodeError::FieldTooLong {
+            field: "prefix",
+            len: prefix_bytes.len(),
+        })?;
+
+        let significant_start = self.id.iter().position(|b| *b != 0).unwrap_or(N);
+        let significant = &self.id[significant_start..];
+        let id_len = u8::try_from(significant.len()).map_err(|_| DecodeError::FieldTooLong {
+            field: "id",
+            len: significant.len(),
+        })?;
+
+        let mut out = Vec::with_capacity(1 + prefix_bytes.len() + 8 + 1 + significant.len());
+        out.push(prefix_len);
+        out.extend_from_slice(prefix_bytes);
+        out.extend_from_slice(&self.timestamp.value().to_be_bytes());
+        out.push(id_len);
+        out.extend_from_slice(significant);
+        Ok(out)
+    }
+
+    /// Reverses [`ByteCounter::encode`], left-padding the significant id
+    /// bytes back to width `N`.
+    pub fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut idx = 0;
+
+        let prefix_len = *bytes.get(idx).ok_or(DecodeError::Truncated)? as usize;
+        idx += 1;
+
+        let prefix_bytes = bytes
+            .get(idx..idx + prefix_len)
+            .ok_or(DecodeError::Truncated)?;
+        idx += prefix_len;
+
+        let prefix = if prefix_bytes.is_empty() {
+            None
+        } else {
+            Some(String::from_utf8(prefix_bytes.to_vec()).map_err(DecodeError::InvalidUtf8)?)
+        };
+
+        let timestamp_bytes = bytes.get(idx..idx + 8).ok_or(DecodeError::Truncated)?;
+        let mut timestamp_buf = [0u8; 8];
+        timestamp_buf.clone_from_slice(timestamp_bytes);
+        idx += 8;
+
+        let id_len = *bytes.get(idx).ok_or(DecodeError::Truncated)? as usize;
+        idx += 1;
+
+        if id_len > N {
+            return Err(DecodeError::IdTooWide {
+                max: N,
+                actual: id_len,
+            });
+        }
+
+        let significant = bytes
+            .get(idx..idx + id_len)
+            .ok_or(DecodeError::Truncated)?;
+
+        let mut id = [0u8; N];
+        id[N - id_len..].copy_from_slice(significant);
+
+        Ok(Self {
+            prefix,
+            timestamp: Timestamp::from(u64::from_be_bytes(timestamp_buf)),
+            id,
+            valid: true,
+        })
+    }
+}
+
+impl<const N: usize> ByteCounter<N> {
     pub fn max() -> Self {
-        let id = [u8::MAX; IDENTIFIER_SIZE];
+        let id = [u8::MAX; N];
 
         Self {
             prefix: None,
-            timestamp: Timestamp::new(),
+            timestamp: Timestamp::default(),
             id,
             valid: true,
         }
     }
 
     pub fn max_with_prefix(prefix: String) -> Self {
-        let id = [u8::MAX; IDENTIFIER_SIZE];
+        let id = [u8::MAX; N];
 
         Self {
             prefix: Some(prefix),
-            timestamp: Timestamp::new(),
+            timestamp: Timestamp::default(),
             id,
             valid: true,
         }
@@ -221,7 +562,28 @@ impl ByteCounter {
 
         Self {
             prefix: self.prefix.clone(),
-            timestamp: Timestamp::new(),
+            timestamp: Timestamp::default(),
+            id: next_id,
+            valid: true,
+        }
+    }
+
+    /// Like [`ByteCounter::next_id`], but timestamps the result using
+    /// `clock` instead of the system clock.
+    pub fn next_id_with(&self, clock: &impl Clock) -> Self {
+        let mut next_id = self.id;
+        for byte in next_id.iter_mut().rev() {
+            if *byte == u8::MAX {
+                *byte = 0
+            } else {
+                *byte += 1;
+                break;
+            }
+        }
+
+        Self {
+            prefix: self.prefix.clone(),
+            timestamp: Timestamp::from_clock(clock),
             id: next_id,
             valid: true,
         }
@@ -240,32 +602,29 @@ impl ByteCounter {
 
         Self {
             prefix: self.prefix.clone(),
-            timestamp: Timestamp::new(),
+            timestamp: Timestamp::default(),
             id: next_id,
             valid: true,
         }
     }
 
+    /// Interprets `id` as a big-endian base-256 integer and returns its
+    /// value, i.e. `sum(id[i] * 256^(N-1-i))`.
+    ///
+    /// Only meaningful for `N <= 16`; wider identifiers don't fit in a
+    /// `u128` and the high-order bytes are silently dropped.
     pub fn to_u128(&self) -> u128 {
         let mut result: u128 = 0;
-        for byte in self.id.iter().rev() {
-            let deref = *byte as u128;
-            if deref == 0 {
-                break;
-            }
-            if result == 0 {
-                result = deref;
-                continue;
-            } else {
-                // TODO: Check for overflow
-                result *= deref;
-            }
+        for byte in self.id.iter() {
+            result = (result << 8) | *byte as u128;
         }
 
         result
     }
 
-    pub fn distance(&self, other: &ByteCounter) -> u128 {
+    /// The absolute difference between two counters' [`ByteCounter::to_u128`]
+    /// values. Inherits `to_u128`'s `N <= 16` limitation.
+    pub fn distance(&self, other: &ByteCounter<N>) -> u128 {
         let lhs = self.to_u128();
         let rhs = other.to_u128();
 
@@ -277,9 +636,12 @@ impl ByteCounter {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
-    use crate::counter::{ByteCounter, IDENTIFIER_SIZE};
+    use core::iter::Step;
+
+    use crate::counter::{ByteCounter, DecodeError, IDENTIFIER_SIZE, KEY_TAG};
+    use crate::timestamp::Timestamp;
 
     #[test]
     fn test_stream_id() {
@@ -288,11 +650,11 @@ mod tests {
         }
 
         {
-            assert_eq!(ByteCounter::default().id, [0, 0, 0, 0, 0, 0, 0, 0]);
+            assert_eq!(ByteCounter::<8>::default().id, [0, 0, 0, 0, 0, 0, 0, 0]);
         }
 
         {
-            let mut bid = ByteCounter::default();
+            let mut bid = ByteCounter::<8>::default();
 
             let next_bid = bid.next_id();
             assert_eq!(next_bid.id, [0, 0, 0, 0, 0, 0, 0, 1]);
@@ -310,7 +672,7 @@ mod tests {
         }
 
         {
-            let mut bid = ByteCounter::new_with_prefix("stream".to_string());
+            let mut bid = ByteCounter::<8>::new_with_prefix("stream".to_string());
             for _ in 0..1e+6 as u64 {
                 bid = bid.next_id();
             }
@@ -321,17 +683,82 @@ mod tests {
             );
 
             let _from = format!("stream:{}:000000000000000015066064", bid.timestamp.value());
-            let _bid: ByteCounter = ByteCounter::from(&_from);
+            let _bid: ByteCounter<8> = ByteCounter::try_from(&_from).unwrap();
             assert_eq!(bid.to_string(), _bid.to_string());
             assert_eq!(Some("stream".to_string()), bid.prefix);
             assert_eq!(bid.timestamp.value(), _bid.timestamp.value());
         }
     }
 
+    #[test]
+    fn test_to_u128_is_positional() {
+        let bid = ByteCounter::<8> {
+            id: [0, 0, 0, 0, 0, 1, 0, 2],
+            ..ByteCounter::<8>::default()
+        };
+
+        assert_eq!(bid.to_u128(), 256u128.pow(2) + 2);
+    }
+
+    #[test]
+    fn test_forward_checked_matches_iterated_next_id() {
+        let mut expected = ByteCounter::<8>::default();
+        for _ in 0..1_000_000u64 {
+            expected = expected.next_id();
+        }
+
+        let actual = Step::forward_checked(ByteCounter::<8>::default(), 1_000_000).unwrap();
+        assert_eq!(actual.id, expected.id);
+        assert_eq!(actual.to_u128(), 1_000_000);
+    }
+
+    #[test]
+    fn test_backward_checked_matches_iterated_prev_id() {
+        let mut expected = ByteCounter::<8>::max();
+        for _ in 0..1_000_000u64 {
+            expected = expected.prev_id();
+        }
+
+        let actual = Step::backward_checked(ByteCounter::<8>::max(), 1_000_000).unwrap();
+        assert_eq!(actual.id, expected.id);
+    }
+
+    #[test]
+    fn test_forward_checked_overflow_returns_none() {
+        assert!(Step::forward_checked(ByteCounter::<8>::max(), 1).is_none());
+    }
+
+    #[test]
+    fn test_backward_checked_underflow_returns_none() {
+        assert!(Step::backward_checked(ByteCounter::<8>::default(), 1).is_none());
+    }
+
+    #[test]
+    fn test_forward_backward_checked_zero_count_is_identity() {
+        let start = ByteCounter::<8> {
+            valid: false,
+            ..ByteCounter::<8>::new_with_prefix("stream".to_string())
+        };
+
+        assert_eq!(Step::forward_checked(start.clone(), 0), Some(start.clone()));
+        assert_eq!(Step::backward_checked(start.clone(), 0), Some(start));
+    }
+
+    #[test]
+    fn test_steps_between_ignores_timestamp() {
+        let start = ByteCounter::<8>::default();
+        let end = ByteCounter::<8> {
+            timestamp: Timestamp::from("1"),
+            ..ByteCounter::<8>::default()
+        };
+
+        assert_eq!(Step::steps_between(&start, &end), Some(0));
+    }
+
     #[test]
     fn test_distance() {
         {
-            let default = ByteCounter::default();
+            let default = ByteCounter::<8>::default();
             let nextnext = default.next_id().next_id();
 
             assert_eq!(default.to_u128(), 0);
@@ -340,8 +767,8 @@ mod tests {
         }
 
         {
-            let default = ByteCounter::default();
-            let mut default2 = ByteCounter::default();
+            let default = ByteCounter::<8>::default();
+            let mut default2 = ByteCounter::<8>::default();
 
             default2 = default2.next_id();
             default2 = default2.next_id();
@@ -352,4 +779,170 @@ mod tests {
             assert_eq!(default.distance(&default2), 4);
         }
     }
+
+    #[test]
+    fn test_key_bytes_roundtrip() {
+        {
+            let bid = ByteCounter::<8>::new_with_prefix("stream".to_string());
+            let bytes = bid.to_key_bytes();
+            let decoded = ByteCounter::<8>::from_key_bytes(&bytes).unwrap();
+
+            assert_eq!(bid.prefix, decoded.prefix);
+            assert_eq!(bid.timestamp.value(), decoded.timestamp.value());
+            assert_eq!(bid.id, decoded.id);
+        }
+
+        {
+            let bid = ByteCounter::<8>::new_with_prefix("has\0null".to_string()).next_id();
+            let bytes = bid.to_key_bytes();
+            let decoded = ByteCounter::<8>::from_key_bytes(&bytes).unwrap();
+
+            assert_eq!(bid.prefix, decoded.prefix);
+            assert_eq!(bid.id, decoded.id);
+        }
+
+        {
+            let bid = ByteCounter::<8>::default();
+            let bytes = bid.to_key_bytes();
+            let decoded = ByteCounter::<8>::from_key_bytes(&bytes).unwrap();
+
+            assert_eq!(None, decoded.prefix);
+        }
+    }
+
+    #[test]
+    fn test_from_key_bytes_rejects_malformed_input() {
+        assert!(matches!(
+            ByteCounter::<8>::from_key_bytes(&[]),
+            Err(DecodeError::Truncated)
+        ));
+
+        let bid = ByteCounter::<8>::new_with_prefix("stream".to_string());
+        let mut truncated = bid.to_key_bytes();
+        truncated.truncate(truncated.len() - 1);
+        assert!(matches!(
+            ByteCounter::<8>::from_key_bytes(&truncated),
+            Err(DecodeError::Truncated)
+        ));
+
+        let mut unterminated = vec![KEY_TAG];
+        unterminated.extend_from_slice(b"stream");
+        assert!(matches!(
+            ByteCounter::<8>::from_key_bytes(&unterminated),
+            Err(DecodeError::Truncated)
+        ));
+
+        let malformed_escape = vec![KEY_TAG, 0x00, 0x01];
+        assert!(matches!(
+            ByteCounter::<8>::from_key_bytes(&malformed_escape),
+            Err(DecodeError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn test_key_bytes_order_preserving() {
+        let lower = ByteCounter::<8>::new_with_prefix("a".to_string());
+        let higher = ByteCounter::<8>::new_with_prefix("b".to_string());
+        assert!(lower.to_key_bytes() < higher.to_key_bytes());
+
+        let first = ByteCounter::<8>::default();
+        let mut second = first.clone();
+        for _ in 0..10 {
+            second = second.next_id();
+        }
+        assert!(first.to_key_bytes() < second.to_key_bytes());
+    }
+
+    #[test]
+    fn test_key_bytes_prefix_is_not_ambiguous_with_escaped_null() {
+        let short = ByteCounter::<8> {
+            timestamp: Timestamp::from(0xFF01000000000000u64),
+            ..ByteCounter::<8>::new_with_prefix("a".to_string())
+        };
+        let long = ByteCounter::<8> {
+            timestamp: Timestamp::from(0u64),
+            ..ByteCounter::<8>::new_with_prefix("a\0".to_string())
+        };
+
+        assert!(short.to_key_bytes() < long.to_key_bytes());
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        {
+            let bid = ByteCounter::<8>::new_with_prefix("stream".to_string());
+            let bytes = bid.encode().unwrap();
+            let decoded = ByteCounter::<8>::decode(&bytes).unwrap();
+
+            assert_eq!(bid.prefix, decoded.prefix);
+            assert_eq!(bid.timestamp.value(), decoded.timestamp.value());
+            assert_eq!(bid.id, decoded.id);
+        }
+
+        {
+            let bid = ByteCounter::<8>::default();
+            let bytes = bid.encode().unwrap();
+            let decoded = ByteCounter::<8>::decode(&bytes).unwrap();
+
+            assert_eq!(None, decoded.prefix);
+            assert_eq!(bid.id, decoded.id);
+        }
+    }
+
+    #[test]
+    fn test_encode_is_compact() {
+        let mut bid = ByteCounter::<8>::new_with_prefix("stream".to_string());
+        for _ in 0..1e+6 as u64 {
+            bid = bid.next_id();
+        }
+
+        assert!(bid.encode().unwrap().len() < bid.to_string().len());
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert!(matches!(
+            ByteCounter::<8>::decode(&[]),
+            Err(DecodeError::Truncated)
+        ));
+
+        let mut truncated = ByteCounter::<8>::default().encode().unwrap();
+        truncated.truncate(truncated.len() - 1);
+        assert!(matches!(
+            ByteCounter::<8>::decode(&truncated),
+            Err(DecodeError::Truncated)
+        ));
+
+        let oversized = vec![0u8, 0, 0, 0, 0, 0, 0, 0, 0, 9];
+        assert!(matches!(
+            ByteCounter::<8>::decode(&oversized),
+            Err(DecodeError::IdTooWide { max: 8, actual: 9 })
+        ));
+    }
+
+    #[test]
+    fn test_encode_rejects_oversized_prefix() {
+        let prefix = "a".repeat(256);
+        let bid = ByteCounter::<8>::new_with_prefix(prefix);
+        assert!(matches!(
+            bid.encode(),
+            Err(DecodeError::FieldTooLong {
+                field: "prefix",
+                len: 256
+            })
+        ));
+    }
+
+    #[test]
+    fn test_decode_bytes_rejects_malformed_length() {
+        assert!(matches!(
+            ByteCounter::<8>::decode_bytes("1"),
+            Err(DecodeError::Malformed)
+        ));
+
+        assert!(matches!(
+            ByteCounter::<8>::try_from(&"x:100:45".to_string()),
+            Err(DecodeError::Malformed)
+        ));
+    }
 }
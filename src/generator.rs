@@ -1,12 +1,14 @@
-use crate::counter::ByteCounter;
+use alloc::string::String;
+
+use crate::counter::{ByteCounter, IDENTIFIER_SIZE};
 
 #[derive(Clone, Debug)]
-pub struct Generator {
-    current: ByteCounter,
-    end: ByteCounter,
+pub struct Generator<const N: usize = IDENTIFIER_SIZE> {
+    current: ByteCounter<N>,
+    end: ByteCounter<N>,
 }
 
-impl Generator {
+impl<const N: usize> Generator<N> {
     pub fn new() -> Self {
         Generator {
             current: ByteCounter::new(),
@@ -22,14 +24,14 @@ impl Generator {
     }
 }
 
-impl Default for Generator {
+impl<const N: usize> Default for Generator<N> {
     fn default() -> Self {
         Generator::new()
     }
 }
 
-impl Iterator for Generator {
-    type Item = ByteCounter;
+impl<const N: usize> Iterator for Generator<N> {
+    type Item = ByteCounter<N>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.current < self.end {
@@ -42,7 +44,7 @@ impl Iterator for Generator {
     }
 }
 
-impl DoubleEndedIterator for Generator {
+impl<const N: usize> DoubleEndedIterator for Generator<N> {
     fn next_back(&mut self) -> Option<Self::Item> {
         if self.current < self.end {
             let result = self.end.clone();
@@ -54,7 +56,7 @@ impl DoubleEndedIterator for Generator {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use crate::counter::ByteCounter;
     use crate::generator::Generator;
@@ -65,8 +67,8 @@ mod tests {
         let prefix: String = Alphanumeric.sample_string(&mut rand::thread_rng(), 16);
 
         {
-            let generator = Generator::new();
-            let mut expected = ByteCounter::default();
+            let generator = Generator::<8>::new();
+            let mut expected = ByteCounter::<8>::default();
             for item in generator.take(1000) {
                 assert_eq!(item, expected);
                 assert_eq!(item.prefix, None);
@@ -75,8 +77,8 @@ mod tests {
         }
 
         {
-            let generator = Generator::new();
-            let mut expected = ByteCounter::max();
+            let generator = Generator::<8>::new();
+            let mut expected = ByteCounter::<8>::max();
             for item in generator.rev().take(100) {
                 assert_eq!(item, expected);
                 assert_eq!(item.prefix, None);
@@ -85,8 +87,8 @@ mod tests {
         }
 
         {
-            let generator = Generator::new_with_prefix(prefix.clone());
-            let mut expected = ByteCounter::new_with_prefix(prefix.clone());
+            let generator = Generator::<8>::new_with_prefix(prefix.clone());
+            let mut expected = ByteCounter::<8>::new_with_prefix(prefix.clone());
             for item in generator.take(100) {
                 assert_eq!(item, expected);
                 assert_eq!(item.prefix, Some(prefix.clone()));
@@ -95,8 +97,8 @@ mod tests {
         }
 
         {
-            let generator = Generator::new_with_prefix(prefix.clone());
-            let mut expected = ByteCounter::max_with_prefix(prefix.clone());
+            let generator = Generator::<8>::new_with_prefix(prefix.clone());
+            let mut expected = ByteCounter::<8>::max_with_prefix(prefix.clone());
             for item in generator.rev().take(100) {
                 println!("{:?}", item.to_string());
                 assert_eq!(item, expected);
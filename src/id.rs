@@ -1,10 +1,23 @@
-use std::mem;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
 
 use serde_derive::{Deserialize, Serialize};
 
+use crate::counter::DecodeError;
+
 pub const IDENTIFIER_SIZE: usize = mem::size_of::<u128>();
 
-#[derive(Debug, Serialize, Deserialize, Clone, Hash, PartialEq, Eq)]
+/// 128-bit identifier, kept as its own type (rather than an alias over the
+/// const-generic [`crate::counter::ByteCounter`]) for wire- and
+/// source-compatibility with code written against the old standalone
+/// `BigID` type: no `timestamp` field, `"prefix:id"`/`"id"` string
+/// encoding, and the old `new`/`next` method names.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct BigID {
     pub id: [u8; IDENTIFIER_SIZE],
     pub prefix: Option<String>,
@@ -15,33 +28,37 @@ pub struct BigID {
 
 impl BigID {
     pub fn new(prefix: Option<String>) -> Self {
-        let id = [0; IDENTIFIER_SIZE];
-
         Self {
-            id,
+            id: [0; IDENTIFIER_SIZE],
             prefix,
             valid: true,
         }
     }
 
-    pub fn decode_bytes(str_bytes: &str) -> Self {
+    /// Parses the `to_string()` decimal-triplet `id` encoding back into
+    /// bytes, returning a [`DecodeError`] instead of panicking on
+    /// malformed input.
+    pub fn decode_bytes(str_bytes: &str) -> Result<Self, DecodeError> {
+        if !str_bytes.len().is_multiple_of(3) {
+            return Err(DecodeError::Malformed);
+        }
+
         let byte_count = str_bytes.len() / 3;
         let _aligned = match byte_count.cmp(&IDENTIFIER_SIZE) {
-            std::cmp::Ordering::Less => {
+            core::cmp::Ordering::Less => {
                 let prefix_size = IDENTIFIER_SIZE - byte_count;
-                let result = format!(
+                format!(
                     "{0}{1}",
-                    String::from_utf8(vec![b'0'; prefix_size * 3]).unwrap(),
+                    String::from_utf8(vec![b'0'; prefix_size * 3]).map_err(DecodeError::InvalidUtf8)?,
                     str_bytes
-                );
-                result
+                )
             }
-            std::cmp::Ordering::Equal => str_bytes.to_string(),
-            std::cmp::Ordering::Greater => {
+            core::cmp::Ordering::Equal => str_bytes.to_string(),
+            core::cmp::Ordering::Greater => {
                 let start_idx = str_bytes.len() - (IDENTIFIER_SIZE * 3);
                 let sl = str_bytes.as_bytes().to_owned();
                 let slice = &sl[start_idx..];
-                String::from_utf8(slice.to_vec()).unwrap()
+                String::from_utf8(slice.to_vec()).map_err(DecodeError::InvalidUtf8)?
             }
         };
 
@@ -49,26 +66,33 @@ impl BigID {
             .chars()
             .collect::<Vec<char>>()
             .chunks(3)
-            .map(|c| c.iter().collect::<String>().parse::<u8>().unwrap())
-            .collect::<Vec<u8>>();
+            .map(|c| {
+                c.iter()
+                    .collect::<String>()
+                    .parse::<u8>()
+                    .map_err(|_| DecodeError::InvalidDigits(c.iter().collect()))
+            })
+            .collect::<Result<Vec<u8>, DecodeError>>()?;
+
+        if __bytes.len() != IDENTIFIER_SIZE {
+            return Err(DecodeError::Malformed);
+        }
 
         let mut mem_id = [0x0_u8; IDENTIFIER_SIZE];
         mem_id.clone_from_slice(__bytes.as_ref());
 
-        Self {
+        Ok(Self {
             id: mem_id,
-            valid: true,
             prefix: None,
-        }
+            valid: true,
+        })
     }
 }
 
 impl Default for BigID {
     fn default() -> Self {
-        let id = [0; IDENTIFIER_SIZE];
-
         Self {
-            id,
+            id: [0; IDENTIFIER_SIZE],
             prefix: None,
             valid: true,
         }
@@ -96,20 +120,22 @@ impl ToString for BigID {
         if self.prefix.is_some() {
             return format!("{}:{}", self.prefix.clone().unwrap(), _id);
         }
-        format!("{0}", _id)
+        _id
     }
 }
 
-impl From<&str> for BigID {
-    fn from(key: &str) -> Self {
+impl TryFrom<&str> for BigID {
+    type Error = DecodeError;
+
+    fn try_from(key: &str) -> Result<Self, DecodeError> {
         let mut parts = key.split(':').collect::<Vec<&str>>();
 
         if parts.len() == 2 {
             let prefix = parts.remove(0);
             let id = parts.remove(0);
-            let mut obj = BigID::decode_bytes(id);
+            let mut obj = BigID::decode_bytes(id)?;
             obj.prefix = Some(prefix.to_string());
-            return obj;
+            return Ok(obj);
         }
 
         if parts.len() == 1 {
@@ -117,17 +143,111 @@ impl From<&str> for BigID {
             return BigID::decode_bytes(id);
         }
 
-        return Self {
+        Ok(Self {
             id: [0; IDENTIFIER_SIZE],
-            valid: false,
             prefix: None,
-        };
+            valid: false,
+        })
     }
 }
 
-impl From<Box<[u8]>> for BigID {
-    fn from(key: Box<[u8]>) -> Self {
-        return BigID::from(String::from_utf8_lossy(&key).as_ref());
+impl TryFrom<Box<[u8]>> for BigID {
+    type Error = DecodeError;
+
+    fn try_from(key: Box<[u8]>) -> Result<Self, DecodeError> {
+        BigID::try_from(String::from_utf8_lossy(&key).as_ref())
+    }
+}
+
+/// Leading type tag for the memcmp-ordered key encoding produced by
+/// [`BigID::to_key_bytes`].
+pub const KEY_TAG: u8 = 0x02;
+
+impl BigID {
+    /// Encodes this id as an order-preserving binary key: a type tag, the
+    /// prefix as UTF-8 terminated by `0x00 0x00` (interior `0x00` bytes are
+    /// escaped as `0x00 0xFF`, which sorts after the terminator, so no
+    /// encoded prefix is a byte-prefix of another and a prefix sorts before
+    /// any longer prefix that extends it), then the raw (already
+    /// big-endian) `id` bytes.
+    ///
+    /// Two keys compare in the same order as the logical `(prefix, id)`
+    /// tuple, which makes this suitable for use as a key in an LSM/B-tree
+    /// store that orders keys by `memcmp`.
+    pub fn to_key_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(1 + IDENTIFIER_SIZE);
+        out.push(KEY_TAG);
+
+        if let Some(prefix) = &self.prefix {
+            for byte in prefix.as_bytes() {
+                if *byte == 0x00 {
+                    out.push(0x00);
+                    out.push(0xFF);
+                } else {
+                    out.push(*byte);
+                }
+            }
+        }
+        out.push(0x00);
+        out.push(0x00);
+
+        out.extend_from_slice(&self.id);
+        out
+    }
+
+    /// Reverses [`BigID::to_key_bytes`].
+    ///
+    /// Returns a [`DecodeError`] instead of panicking if `bytes` is not a
+    /// well-formed key produced by `to_key_bytes` (missing tag,
+    /// unterminated prefix, or truncated id) — callers scanning untrusted
+    /// keys (e.g. an LSM/B-tree range scan) can skip or report a single
+    /// corrupt key instead of aborting the whole scan.
+    pub fn from_key_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.is_empty() {
+            return Err(DecodeError::Truncated);
+        }
+        let rest = &bytes[1..];
+
+        let mut prefix_bytes = Vec::new();
+        let mut idx = 0;
+        loop {
+            if idx >= rest.len() {
+                return Err(DecodeError::Truncated);
+            }
+            match rest[idx] {
+                0x00 if rest.get(idx + 1) == Some(&0xFF) => {
+                    prefix_bytes.push(0x00);
+                    idx += 2;
+                }
+                0x00 if rest.get(idx + 1) == Some(&0x00) => {
+                    idx += 2;
+                    break;
+                }
+                0x00 => return Err(DecodeError::Malformed),
+                byte => {
+                    prefix_bytes.push(byte);
+                    idx += 1;
+                }
+            }
+        }
+
+        let id_bytes = rest
+            .get(idx..idx + IDENTIFIER_SIZE)
+            .ok_or(DecodeError::Truncated)?;
+        let mut id = [0u8; IDENTIFIER_SIZE];
+        id.clone_from_slice(id_bytes);
+
+        let prefix = if prefix_bytes.is_empty() {
+            None
+        } else {
+            Some(String::from_utf8(prefix_bytes).map_err(DecodeError::InvalidUtf8)?)
+        };
+
+        Ok(Self {
+            id,
+            prefix,
+            valid: true,
+        })
     }
 }
 
@@ -153,24 +273,17 @@ impl BigID {
 
         Self {
             id: next_id,
-            valid: true,
             prefix: self.prefix.clone(),
+            valid: true,
         }
     }
 
+    /// Interprets `id` as a big-endian base-256 integer and returns its
+    /// value, i.e. `sum(id[i] * 256^(IDENTIFIER_SIZE-1-i))`.
     pub fn to_u128(&self) -> u128 {
         let mut result: u128 = 0;
-        for byte in self.id.iter().rev() {
-            let deref = *byte as u128;
-            if deref == 0 {
-                break;
-            }
-            if result == 0 {
-                result = deref;
-                continue;
-            } else {
-                result *= deref;
-            }
+        for byte in self.id.iter() {
+            result = (result << 8) | *byte as u128;
         }
 
         result
@@ -188,9 +301,10 @@ impl BigID {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
-    use crate::id::{BigID, IDENTIFIER_SIZE};
+    use crate::counter::DecodeError;
+    use crate::id::{BigID, IDENTIFIER_SIZE, KEY_TAG};
 
     #[test]
     fn test_stream_id() {
@@ -199,10 +313,7 @@ mod tests {
         }
 
         {
-            assert_eq!(
-                BigID::default().id,
-                [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
-            );
+            assert_eq!(BigID::default().id, [0; 16]);
         }
 
         {
@@ -244,7 +355,7 @@ mod tests {
             );
 
             let _bid: BigID =
-                BigID::from("stream:000000000000000000000000000000000000000015066064");
+                BigID::try_from("stream:000000000000000000000000000000000000000015066064").unwrap();
             assert_eq!(bid.to_string(), _bid.to_string());
             assert_eq!(Some("stream".to_string()), bid.prefix);
         }
@@ -274,4 +385,87 @@ mod tests {
             assert_eq!(default.distance(&default2), 4);
         }
     }
+
+    #[test]
+    fn test_key_bytes_roundtrip() {
+        {
+            let bid = BigID::new(Some("stream".to_string()));
+            let bytes = bid.to_key_bytes();
+            let decoded = BigID::from_key_bytes(&bytes).unwrap();
+
+            assert_eq!(bid.prefix, decoded.prefix);
+            assert_eq!(bid.id, decoded.id);
+        }
+
+        {
+            let bid = BigID::new(Some("has\0null".to_string())).next();
+            let bytes = bid.to_key_bytes();
+            let decoded = BigID::from_key_bytes(&bytes).unwrap();
+
+            assert_eq!(bid.prefix, decoded.prefix);
+            assert_eq!(bid.id, decoded.id);
+        }
+
+        {
+            let bid = BigID::default();
+            let bytes = bid.to_key_bytes();
+            let decoded = BigID::from_key_bytes(&bytes).unwrap();
+
+            assert_eq!(None, decoded.prefix);
+        }
+    }
+
+    #[test]
+    fn test_from_key_bytes_rejects_malformed_input() {
+        assert!(matches!(
+            BigID::from_key_bytes(&[]),
+            Err(DecodeError::Truncated)
+        ));
+
+        let bid = BigID::new(Some("stream".to_string()));
+        let mut truncated = bid.to_key_bytes();
+        truncated.truncate(truncated.len() - 1);
+        assert!(matches!(
+            BigID::from_key_bytes(&truncated),
+            Err(DecodeError::Truncated)
+        ));
+
+        let malformed_escape = vec![KEY_TAG, 0x00, 0x01];
+        assert!(matches!(
+            BigID::from_key_bytes(&malformed_escape),
+            Err(DecodeError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn test_key_bytes_order_preserving() {
+        let lower = BigID::new(Some("a".to_string()));
+        let higher = BigID::new(Some("b".to_string()));
+        assert!(lower.to_key_bytes() < higher.to_key_bytes());
+
+        let first = BigID::default();
+        let second = first.next().next();
+        assert!(first.to_key_bytes() < second.to_key_bytes());
+    }
+
+    #[test]
+    fn test_old_two_part_format_preserves_prefix() {
+        let bid: BigID =
+            BigID::try_from("stream:000000000000000000000000000000000000000000000001").unwrap();
+        assert_eq!(bid.prefix, Some("stream".to_string()));
+        assert!(bid.valid);
+        assert_eq!(bid.to_u128(), 1);
+    }
+
+    #[test]
+    fn test_decode_bytes_rejects_malformed_length() {
+        assert!(matches!(
+            BigID::decode_bytes("1"),
+            Err(DecodeError::Malformed)
+        ));
+        assert!(matches!(
+            BigID::try_from("x:45"),
+            Err(DecodeError::Malformed)
+        ));
+    }
 }
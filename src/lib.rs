@@ -0,0 +1,9 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![feature(step_trait)]
+
+extern crate alloc;
+
+pub mod counter;
+pub mod generator;
+pub mod id;
+pub mod timestamp;
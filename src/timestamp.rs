@@ -1,7 +1,10 @@
+use core::hash::Hash;
+
 use serde_derive::{Deserialize, Serialize};
-use std::hash::Hash;
+#[cfg(feature = "std")]
 use std::time::{SystemTime, UNIX_EPOCH};
 
+#[cfg(feature = "std")]
 pub fn epoch_ns() -> u128 {
     match SystemTime::now().duration_since(UNIX_EPOCH) {
         Ok(time) => time.as_nanos(),
@@ -9,6 +12,7 @@ pub fn epoch_ns() -> u128 {
     }
 }
 
+#[cfg(feature = "std")]
 pub fn epoch_secs() -> u64 {
     match SystemTime::now().duration_since(UNIX_EPOCH) {
         Ok(time) => time.as_secs(),
@@ -19,25 +23,65 @@ pub fn epoch_secs() -> u64 {
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub struct Timestamp(u64);
 
+/// A source of the current time, in whole seconds since the Unix epoch.
+///
+/// Implementing this lets callers timestamp counters without depending on
+/// `std::time::SystemTime`, which keeps the crate usable under `no_std`
+/// and makes timestamped ids deterministic in tests (a fixed or
+/// monotonically-incrementing clock can be injected instead).
+pub trait Clock {
+    fn now_secs(&self) -> u64;
+}
+
+/// Reads the current time via [`epoch_secs`]; only available with the
+/// `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_secs(&self) -> u64 {
+        epoch_secs()
+    }
+}
+
 impl Timestamp {
+    #[cfg(feature = "std")]
     pub fn new() -> Self {
         Self(epoch_secs())
     }
 
+    pub fn from_clock(clock: &impl Clock) -> Self {
+        Self(clock.now_secs())
+    }
+
     pub fn value(&self) -> u64 {
         self.0
     }
 }
 
 impl Default for Timestamp {
+    #[cfg(feature = "std")]
     fn default() -> Self {
         Self::new()
     }
+
+    #[cfg(not(feature = "std"))]
+    fn default() -> Self {
+        Self(0)
+    }
 }
 
 impl From<&str> for Timestamp {
     fn from(s: &str) -> Self {
-        let value = s.parse::<u64>().unwrap_or_else(|_| 0);
+        let value = s.parse::<u64>().unwrap_or(0);
+        Self(value)
+    }
+}
+
+impl From<u64> for Timestamp {
+    fn from(value: u64) -> Self {
         Self(value)
     }
 }